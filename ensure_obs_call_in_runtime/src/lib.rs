@@ -23,6 +23,7 @@ use std::cell::RefCell;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::{Expr, ExprKind, HirId};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
 use rustc_middle::ty::TyCtxt;
 use rustc_span::def_id::DefId;
 
@@ -71,23 +72,30 @@ impl<'tcx> LateLintPass<'tcx> for EnsureObsCallInRuntime {
             push_closure_allowed(expr.hir_id);
         }
 
+        // Code generated by third-party macros can't be wrapped in
+        // `run_with_obs` by the user, so don't flag it.
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+
+        // Free-function and associated-function calls, e.g. `libobs::foo()` or
+        // `Type::assoc_fn()` (the latter is a `QPath::TypeRelative` callee,
+        // resolved the same way as any other path).
         if let ExprKind::Call(func, _) = expr.kind {
             if let ExprKind::Path(qpath) = &func.kind {
                 if let Some(def_id) = cx.qpath_res(qpath, func.hir_id).opt_def_id() {
-                    if is_from_libobs_crate(cx.tcx, def_id) && !currently_allowed() {
-                        cx.span_lint(
-                            ENSURE_OBS_CALL_IN_RUNTIME,
-                            expr.span,
-                            |diag| {
-                                diag.help(
-                                    "wrap libobs calls in runtime.run_with_obs or runtime.run_with_obs_result",
-                                );
-                            },
-                        );
-                    }
+                    lint_if_from_libobs(cx, expr, def_id);
                 }
             }
         }
+
+        // Method calls, e.g. `wrapper.some_method()` where `some_method`
+        // ultimately resolves to a libobs-crate function.
+        if let ExprKind::MethodCall(..) = expr.kind {
+            if let Some(def_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id) {
+                lint_if_from_libobs(cx, expr, def_id);
+            }
+        }
     }
 
     fn check_expr_post(&mut self, _cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
@@ -101,6 +109,14 @@ fn is_from_libobs_crate(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
     tcx.crate_name(def_id.krate).as_str() == "libobs"
 }
 
+fn lint_if_from_libobs(cx: &LateContext<'_>, expr: &Expr<'_>, def_id: DefId) {
+    if is_from_libobs_crate(cx.tcx, def_id) && !currently_allowed() {
+        cx.span_lint(ENSURE_OBS_CALL_IN_RUNTIME, expr.span, |diag| {
+            diag.help("wrap libobs calls in runtime.run_with_obs or runtime.run_with_obs_result");
+        });
+    }
+}
+
 thread_local! {
     static ALLOWED_CLOSURES: RefCell<FxHashSet<HirId>> = RefCell::new(FxHashSet::default());
     static CLOSURE_STACK: RefCell<Vec<bool>> = RefCell::new(Vec::new());
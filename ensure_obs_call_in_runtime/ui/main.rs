@@ -1,5 +1,5 @@
 // Lint should warn on libobs calls outside runtime helpers
-use libobs::obs_get_audio;
+use libobs::{ObsVideoInfo, obs_get_audio};
 
 struct Runtime;
 
@@ -40,4 +40,14 @@ fn test() {
     unsafe {
         obs_get_audio(); // expect warning
     }
+}
+
+fn method_and_assoc_calls(info: ObsVideoInfo) {
+    unsafe {
+        // expect warning: method call that resolves into the libobs crate
+        info.fps_num();
+
+        // expect warning: associated-function call that resolves into the libobs crate
+        let _ = ObsVideoInfo::default();
+    }
 }
\ No newline at end of file
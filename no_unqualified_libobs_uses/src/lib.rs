@@ -20,6 +20,7 @@ extern crate rustc_trait_selection;
 
 use rustc_hir::{Expr, ExprKind, QPath};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
 use rustc_middle::ty::TyCtxt;
 use rustc_span::def_id::DefId;
 
@@ -58,6 +59,12 @@ dylint_linting::declare_late_lint! {
 
 impl<'tcx> LateLintPass<'tcx> for NoUnqualifiedLibobsUses {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        // Code from external macro expansions can't be rewritten to use a
+        // qualified path by the user, so don't flag it.
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+
         // Check if this is a function call or method call
         if let ExprKind::Call(func, _) = expr.kind {
             // Check if the function is a path expression
@@ -0,0 +1,187 @@
+#![feature(rustc_private)]
+#![allow(unused_extern_crates)]
+
+extern crate rustc_arena;
+extern crate rustc_ast;
+extern crate rustc_ast_pretty;
+extern crate rustc_data_structures;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_hir_pretty;
+extern crate rustc_index;
+extern crate rustc_infer;
+extern crate rustc_lexer;
+extern crate rustc_middle;
+extern crate rustc_mir_dataflow;
+extern crate rustc_parse;
+extern crate rustc_span;
+extern crate rustc_target;
+extern crate rustc_trait_selection;
+
+use std::cell::RefCell;
+
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::{Block, BlockCheckMode, Expr, ExprKind, HirId, Mutability, UnOp, Unsafety};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+
+dylint_linting::declare_late_lint! {
+    /// ### What it does
+    ///
+    /// Detects `unsafe { ... }` blocks that contain no operation that actually
+    /// requires `unsafe`.
+    ///
+    /// ### Why is this bad?
+    ///
+    /// An `unsafe` block is a promise to the reader that the code inside
+    /// performs an operation with safety invariants the compiler can't check.
+    /// Wrapping already-safe code in `unsafe` is misleading, and pairs badly
+    /// with `REQUIRE_SAFETY_COMMENTS_ON_UNSAFE`, which would otherwise demand
+    /// a safety comment justifying a block that never needed one.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// let x = unsafe { 1 + 1 };
+    /// ```
+    ///
+    /// Use instead:
+    ///
+    /// ```rust
+    /// let x = 1 + 1;
+    /// ```
+    pub REDUNDANT_UNSAFE_BLOCK,
+    Warn,
+    "unsafe block that contains no operation requiring unsafe"
+}
+
+impl<'tcx> LateLintPass<'tcx> for RedundantUnsafeBlock {
+    fn check_block(&mut self, _cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
+        if matches!(block.rules, BlockCheckMode::UnsafeBlock(_)) {
+            push_block(block.hir_id);
+        }
+    }
+
+    fn check_block_post(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
+        if !matches!(block.rules, BlockCheckMode::UnsafeBlock(_)) {
+            return;
+        }
+
+        let Some(used) = pop_block(block.hir_id) else {
+            return;
+        };
+
+        if !used && !in_external_macro(cx.sess(), block.span) {
+            cx.span_lint(REDUNDANT_UNSAFE_BLOCK, block.span, |diag| {
+                diag.help("this `unsafe` block contains no operation that requires unsafe; consider removing it");
+            });
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Closure(_) = expr.kind {
+            push_barrier();
+        }
+
+        if is_unsafe_operation(cx, expr) {
+            mark_innermost_block_used();
+        }
+    }
+
+    fn check_expr_post(&mut self, _cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Closure(_) = expr.kind {
+            pop_barrier();
+        }
+    }
+}
+
+/// Returns `true` if `expr` is itself an operation that requires an
+/// enclosing `unsafe` block: a call to an unsafe function or method, a
+/// raw-pointer dereference, a read/write of a `static mut`, a union field
+/// access, or inline asm.
+fn is_unsafe_operation(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Call(func, _) => {
+            if let ExprKind::Path(qpath) = &func.kind {
+                cx.qpath_res(qpath, func.hir_id)
+                    .opt_def_id()
+                    .is_some_and(|def_id| {
+                        cx.tcx.fn_sig(def_id).skip_binder().unsafety() == Unsafety::Unsafe
+                    })
+            } else {
+                false
+            }
+        }
+        ExprKind::MethodCall(..) => cx
+            .typeck_results()
+            .type_dependent_def_id(expr.hir_id)
+            .is_some_and(|def_id| cx.tcx.fn_sig(def_id).skip_binder().unsafety() == Unsafety::Unsafe),
+        ExprKind::Unary(UnOp::Deref, inner) => cx.typeck_results().expr_ty_adjusted(inner).is_unsafe_ptr(),
+        ExprKind::Path(qpath) => matches!(
+            cx.qpath_res(qpath, expr.hir_id),
+            Res::Def(DefKind::Static { mutability: Mutability::Mut, .. }, _)
+        ),
+        ExprKind::Field(base, _) => cx.typeck_results().expr_ty_adjusted(base).is_union(),
+        ExprKind::InlineAsm(_) => true,
+        _ => false,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Frame {
+    UnsafeBlock { hir_id: HirId, used: bool },
+    // A closure is a separate unsafe context: operations inside it must not
+    // be credited to whatever unsafe block happens to lexically contain it.
+    Barrier,
+}
+
+thread_local! {
+    static STACK: RefCell<Vec<Frame>> = RefCell::new(Vec::new());
+}
+
+fn push_block(hir_id: HirId) {
+    STACK.with(|stack| {
+        stack.borrow_mut().push(Frame::UnsafeBlock { hir_id, used: false });
+    });
+}
+
+fn pop_block(hir_id: HirId) -> Option<bool> {
+    STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        match stack.pop() {
+            Some(Frame::UnsafeBlock { hir_id: popped_id, used }) if popped_id == hir_id => Some(used),
+            Some(frame) => {
+                // Mismatched pop shouldn't happen, but don't lose the frame.
+                stack.push(frame);
+                None
+            }
+            None => None,
+        }
+    })
+}
+
+fn push_barrier() {
+    STACK.with(|stack| stack.borrow_mut().push(Frame::Barrier));
+}
+
+fn pop_barrier() {
+    STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        if matches!(stack.last(), Some(Frame::Barrier)) {
+            stack.pop();
+        }
+    });
+}
+
+fn mark_innermost_block_used() {
+    STACK.with(|stack| {
+        if let Some(Frame::UnsafeBlock { used, .. }) = stack.borrow_mut().last_mut() {
+            *used = true;
+        }
+    });
+}
+
+#[test]
+fn ui() {
+    dylint_testing::ui_test_example(env!("CARGO_PKG_NAME"), "ui");
+}
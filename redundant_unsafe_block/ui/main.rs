@@ -0,0 +1,57 @@
+// Lint should warn on unsafe blocks that don't contain any unsafe operation
+
+unsafe fn dangerous() {}
+
+fn raw_ptr_deref(p: *const i32) -> i32 {
+    // No warning: dereferencing a raw pointer requires unsafe
+    unsafe { *p }
+}
+
+fn call_unsafe_fn() {
+    // No warning: calling an unsafe fn requires unsafe
+    unsafe { dangerous() };
+}
+
+struct Wrapper(Vec<i32>);
+
+impl Wrapper {
+    unsafe fn set_len(&mut self, new_len: usize) {
+        self.0.set_len(new_len);
+    }
+}
+
+fn call_unsafe_method(w: &mut Wrapper) {
+    // No warning: calling an unsafe method requires unsafe
+    unsafe { w.set_len(0) };
+}
+
+fn main() {
+    // expect warning: redundant unsafe block
+    let _ = unsafe { 1 + 1 };
+
+    // expect warning: redundant unsafe block
+    unsafe {
+        println!("nothing unsafe happening here");
+    }
+
+    // No warning: the outer block performs the deref directly
+    let p: *const i32 = &1;
+    unsafe {
+        let _ = *p;
+    }
+
+    // expect warning: the outer block itself needs nothing; the inner
+    // nested unsafe block is the one that actually dereferences
+    unsafe {
+        unsafe {
+            let _ = *p;
+        }
+    }
+
+    // expect warning: a closure is a separate unsafe context, so the unsafe
+    // operation inside it doesn't justify this outer block being unsafe too
+    unsafe {
+        let f = || unsafe { *p };
+        f();
+    }
+}
@@ -18,9 +18,17 @@ extern crate rustc_span;
 extern crate rustc_target;
 extern crate rustc_trait_selection;
 
-use rustc_hir::{Block, BlockCheckMode, Item, ItemKind};
+use rustc_hir::{Block, BlockCheckMode, Item, ItemKind, Unsafety};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
-use rustc_span::BytePos;
+use rustc_middle::lint::in_external_macro;
+use rustc_span::{Span, SyntaxContext};
+
+/// Skips spans coming from external macro expansions (where the user has no
+/// way to add a SAFETY comment) as well as any non-root syntax context, since
+/// the source snippet we'd scan for macro-generated code is meaningless.
+fn in_ignored_context(cx: &LateContext<'_>, span: Span) -> bool {
+    in_external_macro(cx.sess(), span) || span.ctxt() != SyntaxContext::root()
+}
 
 dylint_linting::declare_late_lint! {
     /// ### What it does
@@ -59,6 +67,10 @@ dylint_linting::declare_late_lint! {
 
 impl<'tcx> LateLintPass<'tcx> for RequireSafetyCommentsOnUnsafe {
     fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if in_ignored_context(cx, item.span) {
+            return;
+        }
+
         if let ItemKind::Fn { sig: fn_sig, .. } = &item.kind {
             // Check if the function is unsafe
             if fn_sig.header.is_unsafe() {
@@ -73,9 +85,26 @@ impl<'tcx> LateLintPass<'tcx> for RequireSafetyCommentsOnUnsafe {
                 }
             }
         }
+
+        if let ItemKind::Impl(impl_) = &item.kind {
+            // `unsafe impl Trait for Type {}` asserts that the trait's invariants
+            // hold for this type; that assertion deserves the same justification
+            // an unsafe function or block would.
+            if matches!(impl_.unsafety, Unsafety::Unsafe) && !has_safety_doc_comment(cx, item) {
+                cx.span_lint(REQUIRE_SAFETY_COMMENTS_ON_UNSAFE, item.span, |diag| {
+                    diag.help(
+                        "add a safety comment explaining why this unsafe trait impl upholds its invariants",
+                    );
+                });
+            }
+        }
     }
 
     fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
+        if in_ignored_context(cx, block.span) {
+            return;
+        }
+
         // Check for unsafe blocks
         if matches!(block.rules, BlockCheckMode::UnsafeBlock(_)) {
             if !has_safety_comment_before_block(cx, block) {
@@ -92,45 +121,81 @@ impl<'tcx> LateLintPass<'tcx> for RequireSafetyCommentsOnUnsafe {
 }
 
 fn has_safety_doc_comment(cx: &LateContext<'_>, item: &Item<'_>) -> bool {
-    // Get the span for the item and look for safety doc comments above it
+    has_preceding_safety_comment(cx, item.span)
+}
+
+/// Scans backwards from `span`'s start over the blank and comment lines
+/// directly preceding it, looking for a contiguous SAFETY comment. Any other
+/// line, including an attribute, ends the scan.
+///
+/// Kept in sync by hand with the identical copy in
+/// `unnecessary_safety_comment`; there's no shared crate in this tree to
+/// factor it into, so a change here needs the same change there.
+fn has_preceding_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
     let source_map = cx.tcx.sess.source_map();
-    let item_span = item.span;
-    
-    // Get the source file
-    let file = source_map.lookup_source_file(item_span.lo());
-    let file_start = file.start_pos;
-    
-    // Calculate how far back to look (e.g., 1000 characters for doc comments)
-    let search_start = if item_span.lo().0 >= file_start.0 + 1000 {
-        BytePos(item_span.lo().0 - 1000)
-    } else {
-        file_start
+    let file = source_map.lookup_source_file(span.lo());
+
+    let Some(start_line) = file.lookup_line(span.lo()) else {
+        return false;
     };
-    
-    // Create a span from search_start to item_start
-    let search_span = item_span.with_lo(search_start).with_hi(item_span.lo());
-    
-    // Get the text before the item
-    if let Ok(preceding_text) = source_map.span_to_snippet(search_span) {
-        // Look for Safety doc comment in the preceding text
-        // Check for "/// # Safety" or "/** # Safety" patterns
-        if preceding_text.contains("# Safety") {
-            return true;
+
+    // Extend the region backwards only over the unbroken run of blank/comment
+    // lines directly above `span`, instead of re-tokenizing the whole file
+    // prefix on every call. A line that merely ends in `*/` only closes a
+    // block comment; once we see one we must keep consuming lines
+    // unconditionally until we reach the line that opens it with `/*`, since
+    // the comment's interior lines need not look like comments at all.
+    let mut region_lo = file.line_bounds(start_line).start;
+    let mut line = start_line;
+    let mut in_block_comment = false;
+    while line > 0 {
+        let prev_bounds = file.line_bounds(line - 1);
+        let Ok(prev_text) =
+            source_map.span_to_snippet(span.with_lo(prev_bounds.start).with_hi(prev_bounds.end))
+        else {
+            break;
+        };
+        let trimmed = prev_text.trim();
+
+        if in_block_comment {
+            in_block_comment = !trimmed.contains("/*");
+            region_lo = prev_bounds.start;
+            line -= 1;
+            continue;
         }
+
+        if trimmed.is_empty() || trimmed.starts_with("//") || is_single_line_block_comment(trimmed) {
+            region_lo = prev_bounds.start;
+            line -= 1;
+            continue;
+        }
+
+        if trimmed.ends_with("*/") {
+            in_block_comment = !trimmed.contains("/*");
+            region_lo = prev_bounds.start;
+            line -= 1;
+            continue;
+        }
+
+        break;
     }
-    
-    false
+
+    let region = span.with_lo(region_lo).with_hi(span.lo());
+    has_contiguous_safety_comment(cx, region)
+}
+
+/// Whether `trimmed` is a complete `/* ... */` block comment on its own line.
+fn is_single_line_block_comment(trimmed: &str) -> bool {
+    trimmed.len() >= 4 && trimmed.starts_with("/*") && trimmed.ends_with("*/")
 }
 
 fn has_safety_comment_before_block(cx: &LateContext<'_>, block: &Block<'_>) -> bool {
-    let source_map = cx.tcx.sess.source_map();
-    
     // Check if there are any statements in the block
     if block.stmts.is_empty() && block.expr.is_none() {
         // Empty block, no safety comment needed
         return false;
     }
-    
+
     // Get the first statement or expression in the block
     let first_item_span = if let Some(first_stmt) = block.stmts.first() {
         first_stmt.span
@@ -139,30 +204,66 @@ fn has_safety_comment_before_block(cx: &LateContext<'_>, block: &Block<'_>) -> b
     } else {
         return false;
     };
-    
-    // Get the block start (just after opening brace)
-    let block_start = block.span.lo();
-    
-    // Create a span from block start to first item
-    let span_to_check = block.span.with_lo(block_start).with_hi(first_item_span.lo());
-    
-    // Get the text between the opening brace and the first statement
-    if let Ok(text_before_first_item) = source_map.span_to_snippet(span_to_check) {
-        // Look for SAFETY comment in this text
-        for line in text_before_first_item.lines() {
-            let trimmed = line.trim();
-            
-            // Found a SAFETY comment (accept "SAFETY:" and "Safety:" variants)
-            if trimmed.starts_with("// SAFETY:") || 
-               trimmed.starts_with("// Safety:") {
-                return true;
+
+    // Region from the opening brace to the first statement: anything in
+    // between must be an unbroken run of whitespace/comments ending in a
+    // SAFETY comment for it to count.
+    let region = block.span.with_hi(first_item_span.lo());
+
+    has_contiguous_safety_comment(cx, region)
+}
+
+/// Returns `true` if `region` ends in a SAFETY/`# Safety` comment that is
+/// contiguous with the end of the region, i.e. only whitespace and other
+/// comments separate it from whatever follows. Tokenizes the snippet with
+/// `rustc_lexer` and walks backwards from the end, bailing out the moment a
+/// non-comment, non-whitespace token is encountered.
+fn has_contiguous_safety_comment(cx: &LateContext<'_>, region: Span) -> bool {
+    let source_map = cx.tcx.sess.source_map();
+    let Ok(text) = source_map.span_to_snippet(region) else {
+        return false;
+    };
+
+    let mut tokens = Vec::new();
+    let mut pos = 0usize;
+    for token in rustc_lexer::tokenize(&text) {
+        let len = token.len as usize;
+        tokens.push((token.kind, &text[pos..pos + len]));
+        pos += len;
+    }
+
+    for (kind, snippet) in tokens.into_iter().rev() {
+        match kind {
+            rustc_lexer::TokenKind::Whitespace => continue,
+            rustc_lexer::TokenKind::LineComment { .. } | rustc_lexer::TokenKind::BlockComment { .. } => {
+                if is_safety_comment(snippet) {
+                    return true;
+                }
             }
+            _ => return false,
         }
     }
-    
+
     false
 }
 
+/// Checks whether a single comment token (with its delimiters still
+/// attached) is a `SAFETY:`/`Safety:`/`# Safety` notice.
+fn is_safety_comment(comment: &str) -> bool {
+    let inner = comment
+        .trim_start_matches("///")
+        .trim_start_matches("//!")
+        .trim_start_matches("//")
+        .trim_start_matches("/**")
+        .trim_start_matches("/*!")
+        .trim_start_matches("/*")
+        .trim_end_matches("*/")
+        .trim_start_matches('*')
+        .trim();
+
+    inner.starts_with("# Safety") || inner.starts_with("SAFETY:") || inner.starts_with("Safety:")
+}
+
 #[test]
 fn ui() {
     dylint_testing::ui_test_example(env!("CARGO_PKG_NAME"), "ui");
@@ -17,6 +17,14 @@ unsafe fn safe_documented_function() {
     // ...
 }
 
+/* SAFETY:
+   This is fine as long as the caller has already validated the input,
+   which every current call site does.
+*/
+unsafe fn block_comment_documented_function() {
+    // ...
+}
+
 fn main() {
     // expect warning: missing safety comment before call
     unsafe {
@@ -70,3 +78,22 @@ fn external_call() {
         dangerous_function();
     }
 }
+
+unsafe trait Marker {}
+
+struct MyType;
+
+// expect warning: missing safety comment on unsafe impl
+unsafe impl Marker for MyType {}
+
+struct OtherType;
+
+// SAFETY: OtherType has no invalid bit patterns, so this is sound.
+unsafe impl Marker for OtherType {}
+
+struct BlockCommentType;
+
+/* SAFETY:
+   BlockCommentType has no invalid bit patterns either, so this is sound.
+*/
+unsafe impl Marker for BlockCommentType {}
@@ -0,0 +1,310 @@
+#![feature(rustc_private)]
+#![allow(unused_extern_crates)]
+
+extern crate rustc_arena;
+extern crate rustc_ast;
+extern crate rustc_ast_pretty;
+extern crate rustc_data_structures;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_hir_pretty;
+extern crate rustc_index;
+extern crate rustc_infer;
+extern crate rustc_lexer;
+extern crate rustc_middle;
+extern crate rustc_mir_dataflow;
+extern crate rustc_parse;
+extern crate rustc_span;
+extern crate rustc_target;
+extern crate rustc_trait_selection;
+
+use std::cell::RefCell;
+
+use rustc_hir::{Block, BlockCheckMode, Expr, ExprKind, Item, ItemKind, Stmt, StmtKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_span::{Span, SyntaxContext};
+
+/// Skips spans coming from external macro expansions (where the user has no
+/// way to remove a SAFETY comment) as well as any non-root syntax context,
+/// since the source snippet we'd scan for macro-generated code is meaningless.
+fn in_ignored_context(cx: &LateContext<'_>, span: Span) -> bool {
+    in_external_macro(cx.sess(), span) || span.ctxt() != SyntaxContext::root()
+}
+
+dylint_linting::declare_late_lint! {
+    /// ### What it does
+    ///
+    /// Detects `// SAFETY:` / `# Safety` comments attached to code that has no
+    /// unsafe operations at all.
+    ///
+    /// ### Why is this bad?
+    ///
+    /// Safe code has no safety requirements for callers to uphold, so a SAFETY
+    /// comment on it is misleading at best and, at worst, suggests an unsafe
+    /// operation was removed without also removing its justification.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// // SAFETY: this is fine
+    /// fn add_one(x: i32) -> i32 {
+    ///     x + 1
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    ///
+    /// ```rust
+    /// fn add_one(x: i32) -> i32 {
+    ///     x + 1
+    /// }
+    /// ```
+    pub UNNECESSARY_SAFETY_COMMENT,
+    Warn,
+    "SAFETY comment attached to code that is not unsafe"
+}
+
+impl<'tcx> LateLintPass<'tcx> for UnnecessarySafetyComment {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if in_ignored_context(cx, item.span) {
+            return;
+        }
+
+        if let ItemKind::Fn { sig: fn_sig, .. } = &item.kind {
+            if !fn_sig.header.is_unsafe() && has_safety_doc_comment(cx, item) {
+                cx.span_lint(UNNECESSARY_SAFETY_COMMENT, item.span, |diag| {
+                    diag.help("this function is not unsafe; remove the safety comment");
+                });
+            }
+        }
+    }
+
+    fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
+        // Tracked unconditionally (even in an ignored context) so `check_stmt`
+        // always sees a balanced stack for the statements nested inside.
+        push_enclosing_block_unsafe(matches!(block.rules, BlockCheckMode::UnsafeBlock(_)));
+
+        if in_ignored_context(cx, block.span) {
+            return;
+        }
+
+        if !matches!(block.rules, BlockCheckMode::UnsafeBlock(_))
+            && has_safety_comment_before_block(cx, block)
+        {
+            cx.span_lint(UNNECESSARY_SAFETY_COMMENT, block.span, |diag| {
+                diag.help("this block is not unsafe; remove the SAFETY comment");
+            });
+        }
+    }
+
+    fn check_block_post(&mut self, _cx: &LateContext<'tcx>, _block: &'tcx Block<'tcx>) {
+        pop_enclosing_block_unsafe();
+    }
+
+    fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx Stmt<'tcx>) {
+        if in_ignored_context(cx, stmt.span) {
+            return;
+        }
+
+        // Nested items and statements that themselves open an unsafe block are
+        // handled by `check_item`/`check_block` above; don't double report.
+        // Likewise, a statement whose *immediate* enclosing block is already
+        // unsafe relies on that block's own SAFETY comment, so a comment
+        // directly above it is exactly what `REQUIRE_SAFETY_COMMENTS_ON_UNSAFE`
+        // asks for, not something to flag here.
+        if matches!(stmt.kind, StmtKind::Item(_))
+            || starts_unsafe_block(stmt)
+            || enclosing_block_is_unsafe()
+        {
+            return;
+        }
+
+        if has_preceding_safety_comment(cx, stmt.span) {
+            cx.span_lint(UNNECESSARY_SAFETY_COMMENT, stmt.span, |diag| {
+                diag.help("this statement is not unsafe; remove the SAFETY comment");
+            });
+        }
+    }
+}
+
+thread_local! {
+    static ENCLOSING_BLOCK_UNSAFE: RefCell<Vec<bool>> = RefCell::new(Vec::new());
+}
+
+fn push_enclosing_block_unsafe(is_unsafe: bool) {
+    ENCLOSING_BLOCK_UNSAFE.with(|stack| stack.borrow_mut().push(is_unsafe));
+}
+
+fn pop_enclosing_block_unsafe() {
+    ENCLOSING_BLOCK_UNSAFE.with(|stack| {
+        stack.borrow_mut().pop();
+    });
+}
+
+/// Whether the block immediately containing the statement being checked is
+/// itself an `unsafe` block.
+fn enclosing_block_is_unsafe() -> bool {
+    ENCLOSING_BLOCK_UNSAFE.with(|stack| *stack.borrow().last().unwrap_or(&false))
+}
+
+fn starts_unsafe_block(stmt: &Stmt<'_>) -> bool {
+    let expr: &Expr<'_> = match stmt.kind {
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => expr,
+        _ => return false,
+    };
+
+    matches!(
+        expr.kind,
+        ExprKind::Block(block, _) if matches!(block.rules, BlockCheckMode::UnsafeBlock(_))
+    )
+}
+
+fn has_safety_doc_comment(cx: &LateContext<'_>, item: &Item<'_>) -> bool {
+    has_preceding_safety_comment(cx, item.span)
+}
+
+/// Scans backwards from `span`'s start over the blank and comment lines
+/// directly preceding it, looking for a contiguous SAFETY comment. Any other
+/// line, including an attribute, ends the scan.
+///
+/// Kept in sync by hand with the identical copy in
+/// `require_safety_comments_on_unsafe`; there's no shared crate in this tree
+/// to factor it into, so a change here needs the same change there.
+fn has_preceding_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
+    let source_map = cx.tcx.sess.source_map();
+    let file = source_map.lookup_source_file(span.lo());
+
+    let Some(start_line) = file.lookup_line(span.lo()) else {
+        return false;
+    };
+
+    // Extend the region backwards only over the unbroken run of blank/comment
+    // lines directly above `span`, instead of re-tokenizing the whole file
+    // prefix on every call. A line that merely ends in `*/` only closes a
+    // block comment; once we see one we must keep consuming lines
+    // unconditionally until we reach the line that opens it with `/*`, since
+    // the comment's interior lines need not look like comments at all.
+    let mut region_lo = file.line_bounds(start_line).start;
+    let mut line = start_line;
+    let mut in_block_comment = false;
+    while line > 0 {
+        let prev_bounds = file.line_bounds(line - 1);
+        let Ok(prev_text) =
+            source_map.span_to_snippet(span.with_lo(prev_bounds.start).with_hi(prev_bounds.end))
+        else {
+            break;
+        };
+        let trimmed = prev_text.trim();
+
+        if in_block_comment {
+            in_block_comment = !trimmed.contains("/*");
+            region_lo = prev_bounds.start;
+            line -= 1;
+            continue;
+        }
+
+        if trimmed.is_empty() || trimmed.starts_with("//") || is_single_line_block_comment(trimmed) {
+            region_lo = prev_bounds.start;
+            line -= 1;
+            continue;
+        }
+
+        if trimmed.ends_with("*/") {
+            in_block_comment = !trimmed.contains("/*");
+            region_lo = prev_bounds.start;
+            line -= 1;
+            continue;
+        }
+
+        break;
+    }
+
+    let region = span.with_lo(region_lo).with_hi(span.lo());
+    has_contiguous_safety_comment(cx, region)
+}
+
+/// Whether `trimmed` is a complete `/* ... */` block comment on its own line.
+fn is_single_line_block_comment(trimmed: &str) -> bool {
+    trimmed.len() >= 4 && trimmed.starts_with("/*") && trimmed.ends_with("*/")
+}
+
+fn has_safety_comment_before_block(cx: &LateContext<'_>, block: &Block<'_>) -> bool {
+    // Check if there are any statements in the block
+    if block.stmts.is_empty() && block.expr.is_none() {
+        // Empty block, no safety comment needed
+        return false;
+    }
+
+    // Get the first statement or expression in the block
+    let first_item_span = if let Some(first_stmt) = block.stmts.first() {
+        first_stmt.span
+    } else if let Some(expr) = block.expr {
+        expr.span
+    } else {
+        return false;
+    };
+
+    // Region from the opening brace to the first statement: anything in
+    // between must be an unbroken run of whitespace/comments ending in a
+    // SAFETY comment for it to count.
+    let region = block.span.with_hi(first_item_span.lo());
+
+    has_contiguous_safety_comment(cx, region)
+}
+
+/// Returns `true` if `region` ends in a SAFETY/`# Safety` comment that is
+/// contiguous with the end of the region, i.e. only whitespace and other
+/// comments separate it from whatever follows. Tokenizes the snippet with
+/// `rustc_lexer` and walks backwards from the end, bailing out the moment a
+/// non-comment, non-whitespace token is encountered.
+fn has_contiguous_safety_comment(cx: &LateContext<'_>, region: Span) -> bool {
+    let source_map = cx.tcx.sess.source_map();
+    let Ok(text) = source_map.span_to_snippet(region) else {
+        return false;
+    };
+
+    let mut tokens = Vec::new();
+    let mut pos = 0usize;
+    for token in rustc_lexer::tokenize(&text) {
+        let len = token.len as usize;
+        tokens.push((token.kind, &text[pos..pos + len]));
+        pos += len;
+    }
+
+    for (kind, snippet) in tokens.into_iter().rev() {
+        match kind {
+            rustc_lexer::TokenKind::Whitespace => continue,
+            rustc_lexer::TokenKind::LineComment { .. } | rustc_lexer::TokenKind::BlockComment { .. } => {
+                if is_safety_comment(snippet) {
+                    return true;
+                }
+            }
+            _ => return false,
+        }
+    }
+
+    false
+}
+
+/// Checks whether a single comment token (with its delimiters still
+/// attached) is a `SAFETY:`/`Safety:`/`# Safety` notice.
+fn is_safety_comment(comment: &str) -> bool {
+    let inner = comment
+        .trim_start_matches("///")
+        .trim_start_matches("//!")
+        .trim_start_matches("//")
+        .trim_start_matches("/**")
+        .trim_start_matches("/*!")
+        .trim_start_matches("/*")
+        .trim_end_matches("*/")
+        .trim_start_matches('*')
+        .trim();
+
+    inner.starts_with("# Safety") || inner.starts_with("SAFETY:") || inner.starts_with("Safety:")
+}
+
+#[test]
+fn ui() {
+    dylint_testing::ui_test_example(env!("CARGO_PKG_NAME"), "ui");
+}
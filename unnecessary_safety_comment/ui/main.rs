@@ -0,0 +1,39 @@
+// Lint should warn on SAFETY comments attached to code that isn't unsafe
+
+unsafe fn another_dangerous() {
+    // ...
+}
+
+// expect warning: unnecessary safety comment on safe function
+// SAFETY: this is fine
+fn add_one(x: i32) -> i32 {
+    x + 1
+}
+
+fn safe_block_and_statement() {
+    // expect warning: unnecessary safety comment on safe block
+    // SAFETY: nothing unsafe happens here
+    {
+        let _ = 1 + 1;
+    }
+
+    // expect warning: unnecessary safety comment on safe statement
+    // SAFETY: this addition cannot fail
+    let _ = 2 + 2;
+
+    // No warning: the comment justifies an actual unsafe block
+    // SAFETY: all preconditions are met
+    unsafe {
+        another_dangerous();
+    }
+}
+
+fn main() {
+    // No warning: the comment is inside an unsafe block and justifies the
+    // call that follows it, which is exactly what
+    // REQUIRE_SAFETY_COMMENTS_ON_UNSAFE asks for.
+    unsafe {
+        // SAFETY: all preconditions are met
+        another_dangerous();
+    }
+}